@@ -0,0 +1,27 @@
+use std::fmt;
+
+/// The error type returned by all fallible operations in this crate.
+#[derive(Debug)]
+pub struct OllamaError(String);
+
+impl fmt::Display for OllamaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for OllamaError {}
+
+impl From<String> for OllamaError {
+    fn from(message: String) -> Self {
+        Self(message)
+    }
+}
+
+impl From<&str> for OllamaError {
+    fn from(message: &str) -> Self {
+        Self(message.to_string())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, OllamaError>;
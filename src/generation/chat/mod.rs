@@ -4,6 +4,9 @@ use crate::Ollama;
 
 pub mod request;
 
+#[cfg(feature = "stream")]
+mod stream;
+
 use request::ChatMessageRequest;
 
 use super::images::Image;
@@ -11,10 +14,14 @@ use super::images::Image;
 #[cfg(feature = "chat-history")]
 use crate::history::MessagesHistory;
 
+#[cfg(feature = "chat-history")]
+use std::sync::{Arc, Mutex};
+
 #[cfg(feature = "stream")]
 /// A stream of `ChatMessageResponse` objects
-pub type ChatMessageResponseStream =
-    std::pin::Pin<Box<dyn tokio_stream::Stream<Item = Result<ChatMessageResponse, ()>> + Send>>;
+pub type ChatMessageResponseStream = std::pin::Pin<
+    Box<dyn tokio_stream::Stream<Item = Result<ChatMessageResponse, crate::error::OllamaError>> + Send>,
+>;
 
 impl Ollama {
     #[cfg(feature = "stream")]
@@ -24,8 +31,6 @@ impl Ollama {
         &self,
         request: ChatMessageRequest,
     ) -> crate::error::Result<ChatMessageResponseStream> {
-        use tokio_stream::StreamExt;
-
         let mut request = request;
         request.stream = true;
 
@@ -45,24 +50,9 @@ impl Ollama {
             return Err(res.text().await.unwrap_or_else(|e| e.to_string()).into());
         }
 
-        let stream = Box::new(res.bytes_stream().map(|res| match res {
-            Ok(bytes) => {
-                let res = serde_json::from_slice::<ChatMessageResponse>(&bytes);
-                match res {
-                    Ok(res) => Ok(res),
-                    Err(e) => {
-                        eprintln!("Failed to deserialize response: {}", e);
-                        Err(())
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("Failed to read response: {}", e);
-                Err(())
-            }
-        }));
+        let decoded = stream::NdjsonDecoder::new(res.bytes_stream());
 
-        Ok(std::pin::Pin::from(stream))
+        Ok(Box::pin(decoded))
     }
 
     /// Chat message generation.
@@ -128,8 +118,8 @@ impl Ollama {
 
     /// Helper function to store chat messages by id
     fn store_chat_message_by_id(&mut self, id: &str, message: ChatMessage) {
-        if let Some(messages_history) = self.messages_history.as_mut() {
-            messages_history.add_message(id, message);
+        if let Some(messages_history) = self.messages_history.as_ref() {
+            messages_history.lock().unwrap().add_message(id, message);
         }
     }
 
@@ -141,14 +131,13 @@ impl Ollama {
         history_id: &str,
         request_messages: Vec<ChatMessage>,
     ) -> Vec<ChatMessage> {
-        let mut backup = MessagesHistory::default();
+        let backup = Arc::new(Mutex::new(MessagesHistory::default()));
 
         // Clone the current chat messages to avoid borrowing issues
         // And not to add message to the history if the request fails
-        let current_chat_messages = self
-            .messages_history
-            .as_mut()
-            .unwrap_or(&mut backup)
+        let messages_history = self.messages_history.as_ref().unwrap_or(&backup);
+        let mut messages_history = messages_history.lock().unwrap();
+        let current_chat_messages = messages_history
             .messages_by_id
             .entry(history_id.to_string())
             .or_default();
@@ -161,12 +150,135 @@ impl Ollama {
     }
 
     fn remove_history_last_message(&mut self, history_id: &str) {
-        if let Some(history) = self.messages_history.as_mut() {
-            history.pop_last_message_for_id(history_id);
+        if let Some(history) = self.messages_history.as_ref() {
+            history.lock().unwrap().pop_last_message_for_id(history_id);
         }
     }
 }
 
+#[cfg(all(feature = "stream", feature = "chat-history"))]
+impl Ollama {
+    /// Chat message generation with streaming.
+    /// Returns a stream of `ChatMessageResponse` objects
+    /// Manages the history of messages for the given `id`: the deltas are
+    /// concatenated as they arrive, any `tool_calls` carried on a delta are
+    /// collected too, and once the terminal chunk is seen the assembled
+    /// assistant message (content and tool calls) is stored in history. On a
+    /// stream error, the last user message is rolled back, exactly like the
+    /// non-streaming variant.
+    pub async fn send_chat_messages_with_history_stream(
+        &mut self,
+        mut request: ChatMessageRequest,
+        history_id: &str,
+    ) -> crate::error::Result<ChatMessageResponseStream> {
+        use tokio_stream::StreamExt;
+
+        request.messages = self.get_prefill_messages(history_id, request.messages.clone());
+
+        let stream = match self.send_chat_messages_stream(request).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                self.remove_history_last_message(history_id);
+                return Err(e);
+            }
+        };
+
+        let messages_history = self.messages_history.clone();
+        let history_id = history_id.to_string();
+        let mut accumulated = String::new();
+        let mut latest_tool_calls: Option<Vec<ToolCall>> = None;
+        // Set once the reply is stored or the user message is rolled back,
+        // so history is changed at most once per stream.
+        let mut settled = false;
+
+        let stream = stream.map(move |item| match item {
+            Ok(res) => {
+                if let Some(message) = res.message.as_ref() {
+                    accumulated.push_str(&message.content);
+                    update_latest_tool_calls(&mut latest_tool_calls, message);
+                }
+
+                if res.done && !settled {
+                    settled = true;
+                    if let Some(messages_history) = messages_history.as_ref() {
+                        let mut message = ChatMessage::assistant(accumulated.clone());
+                        if let Some(tool_calls) = latest_tool_calls.clone() {
+                            message = message.with_tool_calls(tool_calls);
+                        }
+
+                        messages_history
+                            .lock()
+                            .unwrap()
+                            .add_message(&history_id, message);
+                    }
+                }
+
+                Ok(res)
+            }
+            Err(e) => {
+                if !settled {
+                    settled = true;
+                    if let Some(messages_history) = messages_history.as_ref() {
+                        messages_history
+                            .lock()
+                            .unwrap()
+                            .pop_last_message_for_id(&history_id);
+                    }
+                }
+
+                Err(e)
+            }
+        });
+
+        Ok(Box::pin(stream))
+    }
+}
+
+/// A delta that carries tool calls carries Ollama's complete set, not an
+/// increment, so a delta's non-empty `tool_calls` replaces rather than
+/// extends whatever was accumulated so far.
+#[cfg(feature = "stream")]
+fn update_latest_tool_calls(accumulated: &mut Option<Vec<ToolCall>>, message: &ChatMessage) {
+    if let Some(tool_calls) = message.tool_calls.as_ref().filter(|calls| !calls.is_empty()) {
+        *accumulated = Some(tool_calls.clone());
+    }
+}
+
+#[cfg(feature = "stream")]
+/// Collects a `ChatMessageResponseStream` into a single `ChatMessageResponse`,
+/// concatenating every delta's message content. Useful for callers who want
+/// streaming transport but a single result.
+pub async fn collect_chat_stream(
+    mut stream: ChatMessageResponseStream,
+) -> crate::error::Result<ChatMessageResponse> {
+    use tokio_stream::StreamExt;
+
+    let mut content = String::new();
+    let mut tool_calls: Option<Vec<ToolCall>> = None;
+    let mut final_response: Option<ChatMessageResponse> = None;
+
+    while let Some(item) = stream.next().await {
+        let response = item?;
+
+        if let Some(message) = response.message.as_ref() {
+            content.push_str(&message.content);
+            update_latest_tool_calls(&mut tool_calls, message);
+        }
+
+        final_response = Some(response);
+    }
+
+    let mut response = final_response
+        .ok_or_else(|| crate::error::OllamaError::from("stream produced no response"))?;
+
+    if let Some(message) = response.message.as_mut() {
+        message.content = content;
+        message.tool_calls = tool_calls;
+    }
+
+    Ok(response)
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ChatMessageResponse {
     /// The name of the model used for the completion.
@@ -181,6 +293,24 @@ pub struct ChatMessageResponse {
     pub final_data: Option<ChatMessageFinalResponseData>,
 }
 
+impl ChatMessageResponse {
+    /// Parses the assistant message's content as JSON.
+    /// Intended for use with `Format::Json`/`Format::Schema` requests,
+    /// where the model is constrained to emit valid JSON.
+    pub fn message_json<T: serde::de::DeserializeOwned>(&self) -> crate::error::Result<T> {
+        let content = self
+            .message
+            .as_ref()
+            .ok_or_else(|| crate::error::OllamaError::from("response has no message to parse"))?
+            .content
+            .as_str();
+
+        serde_json::from_str(content).map_err(|e| {
+            crate::error::OllamaError::from(format!("model response was not valid JSON: {e}"))
+        })
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ChatMessageFinalResponseData {
     /// Time spent generating the response
@@ -200,6 +330,13 @@ pub struct ChatMessage {
     pub role: MessageRole,
     pub content: String,
     pub images: Option<Vec<Image>>,
+    /// The tool calls the model made as part of this message, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+    /// The name of the tool whose result this message carries.
+    /// Only relevant for messages with `role: MessageRole::Tool`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
 }
 
 impl ChatMessage {
@@ -208,6 +345,8 @@ impl ChatMessage {
             role,
             content,
             images: None,
+            tool_calls: None,
+            tool_name: None,
         }
     }
 
@@ -223,6 +362,14 @@ impl ChatMessage {
         Self::new(MessageRole::System, content)
     }
 
+    /// Creates a message carrying the result of a tool call, to be sent
+    /// back to the model as part of the next request.
+    pub fn tool(content: String, tool_name: String) -> Self {
+        let mut message = Self::new(MessageRole::Tool, content);
+        message.tool_name = Some(tool_name);
+        message
+    }
+
     pub fn with_images(mut self, images: Vec<Image>) -> Self {
         self.images = Some(images);
         self
@@ -236,6 +383,11 @@ impl ChatMessage {
         }
         self
     }
+
+    pub fn with_tool_calls(mut self, tool_calls: Vec<ToolCall>) -> Self {
+        self.tool_calls = Some(tool_calls);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -246,4 +398,98 @@ pub enum MessageRole {
     Assistant,
     #[serde(rename = "system")]
     System,
+    #[serde(rename = "tool")]
+    Tool,
+}
+
+/// A call the model made to one of the tools it was given in the request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCall {
+    pub function: ToolCallFunction,
+}
+
+/// The name and arguments of a single tool call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolCallFunction {
+    pub name: String,
+    pub arguments: serde_json::Value,
+}
+
+#[cfg(all(test, feature = "stream"))]
+mod stream_tests {
+    use super::*;
+
+    fn response_with_tool_call(done: bool) -> ChatMessageResponse {
+        ChatMessageResponse {
+            model: "llama3".to_string(),
+            created_at: "now".to_string(),
+            message: Some(ChatMessage {
+                role: MessageRole::Assistant,
+                content: "partial".to_string(),
+                images: None,
+                tool_calls: Some(vec![ToolCall {
+                    function: ToolCallFunction {
+                        name: "get_weather".to_string(),
+                        arguments: serde_json::json!({"city": "Paris"}),
+                    },
+                }]),
+                tool_name: None,
+            }),
+            done,
+            final_data: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn collect_chat_stream_does_not_duplicate_tool_calls_across_deltas() {
+        // Ollama repeats the same complete tool_calls set on every delta that
+        // carries it (not incrementally), so two deltas with identical
+        // tool_calls must still collapse to a single call, not two.
+        let deltas: Vec<Result<ChatMessageResponse, crate::error::OllamaError>> =
+            vec![Ok(response_with_tool_call(false)), Ok(response_with_tool_call(true))];
+        let stream: ChatMessageResponseStream = Box::pin(tokio_stream::iter(deltas));
+
+        let collected = collect_chat_stream(stream).await.unwrap();
+
+        let tool_calls = collected.message.unwrap().tool_calls.unwrap();
+        assert_eq!(tool_calls.len(), 1);
+        assert_eq!(tool_calls[0].function.name, "get_weather");
+    }
+}
+
+#[cfg(all(test, feature = "chat-history"))]
+mod history_tests {
+    use super::*;
+
+    #[test]
+    fn with_history_enables_a_non_none_history() {
+        let mut ollama = crate::Ollama::new("http://localhost:11434").with_history();
+
+        let prefill = ollama.get_prefill_messages("conv1", vec![ChatMessage::user("hi".to_string())]);
+        assert_eq!(prefill.len(), 1);
+
+        ollama.store_chat_message_by_id("conv1", ChatMessage::assistant("hello".to_string()));
+
+        let messages_history = ollama
+            .messages_history
+            .clone()
+            .expect("with_history() should populate messages_history");
+        let stored = messages_history.lock().unwrap();
+        let conv = stored.messages_by_id.get("conv1").unwrap();
+        assert_eq!(conv.len(), 2);
+        assert_eq!(conv[0].role, MessageRole::User);
+        assert_eq!(conv[1].role, MessageRole::Assistant);
+    }
+
+    #[test]
+    fn remove_history_last_message_pops_from_a_non_none_history() {
+        let mut ollama = crate::Ollama::new("http://localhost:11434").with_history();
+
+        ollama.get_prefill_messages("conv1", vec![ChatMessage::user("hi".to_string())]);
+        ollama.remove_history_last_message("conv1");
+
+        let messages_history = ollama.messages_history.clone().unwrap();
+        let stored = messages_history.lock().unwrap();
+        assert!(stored.messages_by_id.get("conv1").unwrap().is_empty());
+    }
 }
@@ -0,0 +1,100 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::ChatMessage;
+
+/// A chat message request to Ollama.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessageRequest {
+    pub model: String,
+    pub messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ToolInfo>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<Format>,
+    pub(crate) stream: bool,
+}
+
+impl ChatMessageRequest {
+    pub fn new(model: String, messages: Vec<ChatMessage>) -> Self {
+        Self {
+            model,
+            messages,
+            tools: None,
+            format: None,
+            stream: false, // Stream is always set by the function
+        }
+    }
+
+    /// The tools the model may call while answering this chat request.
+    pub fn tools(mut self, tools: Vec<ToolInfo>) -> Self {
+        self.tools = Some(tools);
+        self
+    }
+
+    /// Constrains the model's response to valid JSON, optionally validated
+    /// against a JSON Schema.
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+}
+
+/// Structured-output mode for a chat request.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Format {
+    /// Forces the response to be plain JSON (`"format":"json"`).
+    Json,
+    /// Forces the response to conform to the given JSON Schema.
+    Schema(serde_json::Value),
+}
+
+impl Serialize for Format {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Format::Json => serializer.serialize_str("json"),
+            Format::Schema(schema) => schema.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Format {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+
+        match value {
+            serde_json::Value::String(s) if s == "json" => Ok(Format::Json),
+            serde_json::Value::String(s) => Err(de::Error::custom(format!(
+                "unsupported format string: {s}"
+            ))),
+            schema => Ok(Format::Schema(schema)),
+        }
+    }
+}
+
+/// A single tool (function) made available to the model for this request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ToolInfo {
+    Function { function: ToolFunctionInfo },
+}
+
+impl ToolInfo {
+    pub fn new(function: ToolFunctionInfo) -> Self {
+        Self::Function { function }
+    }
+}
+
+/// The JSON-Schema description of a function a model can call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolFunctionInfo {
+    pub name: String,
+    pub description: String,
+    /// A JSON Schema object describing the function's parameters.
+    pub parameters: serde_json::Value,
+}
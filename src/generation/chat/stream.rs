@@ -0,0 +1,185 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use serde::Deserialize;
+use tokio_stream::Stream;
+
+use crate::error::OllamaError;
+
+use super::ChatMessageResponse;
+
+/// Mid-stream error payload Ollama sends instead of a `ChatMessageResponse`
+/// line, e.g. `{"error":"model \"foo\" not found"}`.
+#[derive(Deserialize)]
+struct StreamErrorLine {
+    error: String,
+}
+
+/// Decodes a raw byte stream from Ollama's chat endpoint into one
+/// `ChatMessageResponse` per NDJSON line.
+///
+/// Ollama streams one JSON object per line, but a single `bytes_stream`
+/// chunk can contain multiple lines, or split a single line across chunks.
+/// This buffers incoming bytes and only deserializes complete lines,
+/// carrying any partial remainder over to the next poll.
+pub(super) struct NdjsonDecoder {
+    inner: Pin<Box<dyn Stream<Item = reqwest::Result<Bytes>> + Send>>,
+    buffer: Vec<u8>,
+    pending: VecDeque<Vec<u8>>,
+    done: bool,
+}
+
+impl NdjsonDecoder {
+    pub(super) fn new<S>(inner: S) -> Self
+    where
+        S: Stream<Item = reqwest::Result<Bytes>> + Send + 'static,
+    {
+        Self {
+            inner: Box::pin(inner),
+            buffer: Vec::new(),
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn drain_complete_lines(&mut self) {
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let mut line = self.buffer.drain(..=pos).collect::<Vec<u8>>();
+            line.pop(); // drop the trailing '\n'
+            if !line.is_empty() {
+                self.pending.push_back(line);
+            }
+        }
+    }
+}
+
+impl Stream for NdjsonDecoder {
+    type Item = Result<ChatMessageResponse, OllamaError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(line) = self.pending.pop_front() {
+                return Poll::Ready(Some(decode_line(&line)));
+            }
+
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            match self.inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(bytes))) => {
+                    self.buffer.extend_from_slice(&bytes);
+                    self.drain_complete_lines();
+                }
+                Poll::Ready(Some(Err(e))) => {
+                    self.done = true;
+                    return Poll::Ready(Some(Err(OllamaError::from(format!(
+                        "failed to read response: {e}"
+                    )))));
+                }
+                Poll::Ready(None) => {
+                    self.done = true;
+                    if !self.buffer.is_empty() {
+                        let remainder = std::mem::take(&mut self.buffer);
+                        self.pending.push_back(remainder);
+                    }
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+fn decode_line(line: &[u8]) -> Result<ChatMessageResponse, OllamaError> {
+    if let Ok(StreamErrorLine { error }) = serde_json::from_slice::<StreamErrorLine>(line) {
+        return Err(OllamaError::from(error));
+    }
+
+    serde_json::from_slice(line)
+        .map_err(|e| OllamaError::from(format!("failed to deserialize stream chunk: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_line(done: bool) -> String {
+        format!(
+            r#"{{"model":"llama3","created_at":"now","message":{{"role":"assistant","content":"hi"}},"done":{done}}}"#
+        )
+    }
+
+    async fn decode_all(chunks: Vec<reqwest::Result<Bytes>>) -> Vec<Result<ChatMessageResponse, OllamaError>> {
+        use tokio_stream::StreamExt;
+
+        let mut decoder = NdjsonDecoder::new(tokio_stream::iter(chunks));
+        let mut results = Vec::new();
+        while let Some(item) = decoder.next().await {
+            results.push(item);
+        }
+        results
+    }
+
+    #[tokio::test]
+    async fn one_object_split_across_two_chunks() {
+        let line = format!("{}\n", sample_line(true));
+        let mid = line.len() / 2;
+        let chunks = vec![
+            Ok(Bytes::from(line.as_bytes()[..mid].to_vec())),
+            Ok(Bytes::from(line.as_bytes()[mid..].to_vec())),
+        ];
+
+        let results = decode_all(chunks).await;
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_ok());
+    }
+
+    #[tokio::test]
+    async fn multiple_objects_in_one_chunk() {
+        let chunk = format!("{}\n{}\n", sample_line(false), sample_line(true));
+        let results = decode_all(vec![Ok(Bytes::from(chunk.into_bytes()))]).await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].as_ref().is_ok_and(|r| !r.done));
+        assert!(results[1].as_ref().is_ok_and(|r| r.done));
+    }
+
+    #[tokio::test]
+    async fn final_object_with_no_trailing_newline_is_flushed_on_stream_end() {
+        let chunk = sample_line(true); // no trailing '\n'
+        let results = decode_all(vec![Ok(Bytes::from(chunk.into_bytes()))]).await;
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_ok());
+    }
+
+    #[tokio::test]
+    async fn surfaces_mid_stream_server_errors_with_their_own_message() {
+        let chunk = "{\"error\":\"model \\\"foo\\\" not found\"}\n";
+        let results = decode_all(vec![Ok(Bytes::from(chunk.as_bytes().to_vec()))]).await;
+
+        assert_eq!(results.len(), 1);
+        let err = results[0].as_ref().unwrap_err();
+        assert_eq!(err.to_string(), "model \"foo\" not found");
+    }
+
+    #[tokio::test]
+    async fn surfaces_transport_errors_as_ollama_error() {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(200))
+            .build()
+            .unwrap();
+
+        // Port 0 is never a valid connect target, so this fails locally
+        // without depending on network access.
+        let transport_err = client.get("http://127.0.0.1:0").send().await.unwrap_err();
+
+        let results = decode_all(vec![Err(transport_err)]).await;
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+}
@@ -0,0 +1,142 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::OllamaError;
+
+/// An image attached to a chat message, base64-encoded as required by Ollama.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Image {
+    data: String,
+}
+
+impl Image {
+    /// Wraps an already base64-encoded image payload.
+    pub fn from_base64(data: impl Into<String>) -> Self {
+        Self { data: data.into() }
+    }
+
+    /// Reads an image from disk and base64-encodes it.
+    pub fn from_path(path: impl AsRef<Path>) -> crate::error::Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)
+            .map_err(|e| OllamaError::from(format!("failed to read image {path:?}: {e}")))?;
+
+        guess_mime_type(path.extension().and_then(|ext| ext.to_str()), &bytes)?;
+
+        Ok(Self::from_base64(base64_encode(&bytes)))
+    }
+
+    /// Base64-encodes raw image bytes, rejecting anything that doesn't look
+    /// like a supported image by its magic bytes.
+    pub fn from_bytes(bytes: &[u8]) -> crate::error::Result<Self> {
+        guess_mime_type(None, bytes)?;
+
+        Ok(Self::from_base64(base64_encode(bytes)))
+    }
+
+    /// Parses a `data:<mime>;base64,<payload>` URL, or, if the string is
+    /// instead a path to a local file, reads it from disk.
+    pub fn from_data_url(data_url: &str) -> crate::error::Result<Self> {
+        let Some(rest) = data_url.strip_prefix("data:") else {
+            return Self::from_path(data_url);
+        };
+
+        let (header, payload) = rest
+            .split_once(',')
+            .ok_or_else(|| OllamaError::from(format!("malformed data URL: {data_url}")))?;
+
+        if !header.ends_with(";base64") {
+            return Err(OllamaError::from(format!(
+                "unsupported data URL encoding, expected base64: {header}"
+            )));
+        }
+
+        let mime = &header[..header.len() - ";base64".len()];
+        if !mime.starts_with("image/") {
+            return Err(OllamaError::from(format!("unsupported MIME type: {mime}")));
+        }
+
+        Ok(Self::from_base64(payload.to_string()))
+    }
+}
+
+fn base64_encode(bytes: &[u8]) -> String {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    STANDARD.encode(bytes)
+}
+
+/// Confirms the file looks like a supported image, using the extension
+/// where available and falling back to the leading magic bytes.
+fn guess_mime_type(extension: Option<&str>, bytes: &[u8]) -> crate::error::Result<&'static str> {
+    if let Some(ext) = extension {
+        match ext.to_ascii_lowercase().as_str() {
+            "png" => return Ok("image/png"),
+            "jpg" | "jpeg" => return Ok("image/jpeg"),
+            "gif" => return Ok("image/gif"),
+            "webp" => return Ok("image/webp"),
+            _ => {}
+        }
+    }
+
+    match bytes {
+        [0x89, 0x50, 0x4e, 0x47, ..] => Ok("image/png"),
+        [0xff, 0xd8, 0xff, ..] => Ok("image/jpeg"),
+        [0x47, 0x49, 0x46, 0x38, ..] => Ok("image/gif"),
+        [0x52, 0x49, 0x46, 0x46, _, _, _, _, 0x57, 0x45, 0x42, 0x50, ..] => Ok("image/webp"),
+        _ => Err(OllamaError::from(
+            "unsupported or unrecognized image type".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_MAGIC: &[u8] = &[0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a];
+
+    #[test]
+    fn from_bytes_accepts_recognized_magic_bytes() {
+        assert!(Image::from_bytes(PNG_MAGIC).is_ok());
+    }
+
+    #[test]
+    fn from_bytes_rejects_unrecognized_payload() {
+        let err = Image::from_bytes(b"not an image").unwrap_err();
+        assert!(err.to_string().contains("unsupported"));
+    }
+
+    #[test]
+    fn from_data_url_rejects_missing_comma() {
+        let err = Image::from_data_url("data:image/png;base64").unwrap_err();
+        assert!(err.to_string().contains("malformed data URL"));
+    }
+
+    #[test]
+    fn from_data_url_rejects_non_base64_encoding() {
+        let err = Image::from_data_url("data:image/png;utf8,hello").unwrap_err();
+        assert!(err.to_string().contains("expected base64"));
+    }
+
+    #[test]
+    fn from_data_url_rejects_non_image_mime() {
+        let payload = base64_encode(b"hello");
+        let err =
+            Image::from_data_url(&format!("data:text/plain;base64,{payload}")).unwrap_err();
+        assert!(err.to_string().contains("unsupported MIME type"));
+    }
+
+    #[test]
+    fn from_data_url_falls_back_to_local_file() {
+        let path = std::env::temp_dir().join("ollama_rs_image_from_data_url_test.png");
+        std::fs::write(&path, PNG_MAGIC).unwrap();
+
+        let image = Image::from_data_url(path.to_str().unwrap()).unwrap();
+        assert_eq!(image.data, base64_encode(PNG_MAGIC));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
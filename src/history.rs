@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+use crate::generation::chat::ChatMessage;
+
+/// In-memory chat history, keyed by an arbitrary history id chosen by the caller.
+#[derive(Debug, Clone, Default)]
+pub struct MessagesHistory {
+    pub messages_by_id: HashMap<String, Vec<ChatMessage>>,
+}
+
+impl MessagesHistory {
+    pub fn add_message(&mut self, id: &str, message: ChatMessage) {
+        self.messages_by_id
+            .entry(id.to_string())
+            .or_default()
+            .push(message);
+    }
+
+    pub fn pop_last_message_for_id(&mut self, id: &str) {
+        if let Some(messages) = self.messages_by_id.get_mut(id) {
+            messages.pop();
+        }
+    }
+}
@@ -0,0 +1,52 @@
+pub mod error;
+pub mod generation;
+
+#[cfg(feature = "chat-history")]
+pub mod history;
+
+use reqwest::Client;
+
+#[cfg(feature = "chat-history")]
+use std::sync::{Arc, Mutex};
+
+/// A client for interacting with an Ollama server.
+#[derive(Debug, Clone)]
+pub struct Ollama {
+    url: String,
+    reqwest_client: Client,
+    // Shared so a streamed chat, whose history write-back happens after this
+    // struct's borrow has ended, can still record the assembled reply.
+    #[cfg(feature = "chat-history")]
+    messages_history: Option<Arc<Mutex<history::MessagesHistory>>>,
+}
+
+impl Ollama {
+    pub fn new(url: impl Into<String>) -> Self {
+        let mut url = url.into();
+        if !url.ends_with('/') {
+            url.push('/');
+        }
+
+        Self {
+            url,
+            reqwest_client: Client::new(),
+            #[cfg(feature = "chat-history")]
+            messages_history: None,
+        }
+    }
+
+    pub(crate) fn url_str(&self) -> String {
+        self.url.clone()
+    }
+}
+
+#[cfg(feature = "chat-history")]
+impl Ollama {
+    /// Enables in-memory chat history tracking for this client, so
+    /// `send_chat_messages_with_history` and `send_chat_messages_with_history_stream`
+    /// have somewhere to record the running conversation for a given history id.
+    pub fn with_history(mut self) -> Self {
+        self.messages_history = Some(Arc::new(Mutex::new(history::MessagesHistory::default())));
+        self
+    }
+}